@@ -48,6 +48,7 @@ fn as_test(m: u128) -> u64 {
 fn mod_bench(c: &mut Criterion) {
     c.bench_function("mod_u128", |b| b.iter(|| black_box(43567890u128) % black_box(1024u128)));
     c.bench_function("mod_u64", |b| b.iter(|| black_box(43567890u64) % black_box(1024u64)));
+    c.bench_function("mask_u64", |b| b.iter(|| black_box(43567890u64) & (black_box(1024u64) - 1)));
     c.bench_function("add_u64", |b| b.iter(|| black_box(43567890u64) + black_box(1024u64)));
 }
 
@@ -91,6 +92,11 @@ fn bloom_add_bench(c: &mut Criterion) {
 
     c.bench_function("bloom_contains_test", |b| b.iter(|| filter.contains(black_box(hello.as_bytes()))));
     c.bench_function("bloom_not_contains_test", |b| b.iter(|| filter.contains(black_box(b"hellohellohello"))));
+
+    // Power-of-two sizing: the same workload with masking instead of modulo on the hot path.
+    let mut pow2_filter = FilterBuilder::new(items_count as u64, 0.001).build_pow2_bloom_filter();
+    c.bench_function("bloom_add_pow2_test", |b| b.iter(|| bloom_add_test(&mut pow2_filter, black_box("hellohellohellohello".as_bytes()))));
+    c.bench_function("bloom_contains_pow2_test", |b| b.iter(|| pow2_filter.contains(black_box(hello.as_bytes()))));
 }
 
 criterion_group!(benches, bloom_add_bench, hash_bench);
@@ -1,15 +1,23 @@
+use crate::counting::{Counter, CountingBloomFilter};
 use crate::filter::BloomFilter;
 
 #[derive(Clone)]
 #[derive(Debug)]
+#[derive(serde::Serialize, serde::Deserialize)]
 pub struct FilterBuilder {
     expected_elements: u64,
     false_positive_probability: f64,
     pub(crate) size: u64,
     pub(crate) hashes: u32,
+    /// When set, `size` is a power of two so positions are reduced with a mask
+    /// (`hash & (size - 1)`) instead of a modulo.
+    pub(crate) pow2: bool,
     done: bool,
 }
 
+/// The smallest power-of-two `size` (in bits) the power-of-two mode will allocate.
+pub(crate) const POW2_FLOOR: u64 = 512;
+
 pub(crate) const SUFFIX: u64 = 0b0001_1111;
 pub(crate) const MASK: u64 = 0b11111111_11111111_11111111_11111111_11111111_11111111_11111111_11100000;
 
@@ -45,12 +53,31 @@ impl FilterBuilder {
             false_positive_probability,
             size: 0,
             hashes: 0,
+            pow2: false,
             done: false,
         }
     }
 
-    pub(crate) fn from_size_and_hashes(size: u64, hashes: u32) -> Self {
-        todo!()
+    /// Constructs a completed builder directly from a known `size` (bits) and
+    /// `hashes` geometry, e.g. when rehydrating a persisted filter. The
+    /// `expected_elements`/`false_positive_probability` fields are back-solved from
+    /// the optimal-sizing formulas for reporting only — they approximate the
+    /// parameters that would have produced this geometry.
+    pub fn from_size_and_hashes(size: u64, hashes: u32) -> Self {
+        let ln2 = 2f64.ln();
+        // n = m * ln2 / k, the inverse of `optimal_k`.
+        let expected_elements = ((size as f64 * ln2) / hashes as f64).round() as u64;
+        // p = exp(-m * ln2^2 / n), the inverse of `optimal_m`.
+        let false_positive_probability =
+            (-(size as f64) * ln2.powi(2) / expected_elements as f64).exp();
+        FilterBuilder {
+            expected_elements,
+            false_positive_probability,
+            size,
+            hashes,
+            pow2: size.is_power_of_two(),
+            done: true,
+        }
     }
 
     fn expected_elements(&mut self, expected_elements: u64) {
@@ -69,22 +96,57 @@ impl FilterBuilder {
     }
 
 
-    /// todo from_size_and_hashes
     pub(crate) fn complete(&mut self) {
         if !self.done {
             if self.size == 0 {
                 self.size = optimal_m(self.expected_elements, self.false_positive_probability);
                 self.hashes = optimal_k(self.expected_elements, self.size);
+                if self.pow2 {
+                    // Round up to the next power of two (floored at POW2_FLOOR) so
+                    // positions can be masked rather than reduced modulo `size`. The
+                    // hash count stays the one computed for the unrounded size.
+                    self.size = self.size.max(POW2_FLOOR).next_power_of_two();
+                }
             }
             self.done = true;
         }
     }
 
+    /// Opts this builder into power-of-two sizing. When enabled, the computed
+    /// `size` is rounded up to the next power of two (at least [`POW2_FLOOR`] bits)
+    /// so `add`/`contains` reduce hashes with a mask instead of a modulo — slightly
+    /// more memory in exchange for a measurably faster hot path. Must be called
+    /// before the geometry is fixed.
+    pub fn pow2(&mut self, enabled: bool) -> &mut Self {
+        assert!(!self.done, "cannot change sizing mode after the filter geometry is fixed");
+        self.pow2 = enabled;
+        self
+    }
+
     pub fn build_bloom_filter(&mut self) -> BloomFilter {
         self.complete();
         BloomFilter::new(self.clone())
     }
 
+    /// Builds a [`BloomFilter`] in power-of-two sizing mode; see [`pow2`](Self::pow2).
+    pub fn build_pow2_bloom_filter(&mut self) -> BloomFilter {
+        if !self.pow2 {
+            self.pow2(true);
+        }
+        self.complete();
+        BloomFilter::new(self.clone())
+    }
+
+    /// Builds a [`CountingBloomFilter`] with the same `size`/`hashes` geometry as
+    /// [`build_bloom_filter`](Self::build_bloom_filter), but backs each position with
+    /// an N-bit saturating counter so members can be removed. The counter width is
+    /// selected by the type parameter; it defaults to `u8` and can be widened to
+    /// `u16`/`u32` to resist overflow on hot positions.
+    pub fn build_counting_bloom_filter<C: Counter>(&mut self) -> CountingBloomFilter<C> {
+        self.complete();
+        CountingBloomFilter::new(self.clone())
+    }
+
     ///
     pub(crate) fn is_compatible_to(&self, other: &FilterBuilder) -> bool {
         self.size == other.size && self.hashes == other.hashes
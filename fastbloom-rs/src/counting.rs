@@ -0,0 +1,146 @@
+use crate::builder::FilterBuilder;
+use crate::hash::BloomHashIndex;
+
+/// A counter cell backing a single position of a [`CountingBloomFilter`].
+///
+/// Implementors are unsigned integers that saturate instead of wrapping: once a
+/// counter reaches its maximum it is considered "overflowed" and is frozen, so a
+/// later [`CountingBloomFilter::remove`] can never corrupt the membership of the
+/// other items that hash to the same position. `u8` is the default and keeps the
+/// backing store as compact as a byte per position; `u16`/`u32` trade memory for
+/// a higher overflow ceiling.
+pub trait Counter: Copy {
+    /// The largest value the counter can hold.
+    const MAX: Self;
+    /// The empty (zero) counter.
+    const ZERO: Self;
+
+    /// Returns `true` if the counter is zero.
+    fn is_zero(&self) -> bool;
+    /// Returns `true` if the counter has saturated and must no longer be touched.
+    fn is_max(&self) -> bool;
+    /// Increments the counter, saturating at [`Counter::MAX`].
+    fn saturating_inc(&mut self);
+    /// Decrements the counter, saturating at [`Counter::ZERO`].
+    fn saturating_dec(&mut self);
+    /// The counter value as a `u64`, for reporting estimated multiplicities.
+    fn as_u64(&self) -> u64;
+}
+
+macro_rules! impl_counter {
+    ($($t:ty),+) => {
+        $(
+            impl Counter for $t {
+                const MAX: Self = <$t>::MAX;
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn is_zero(&self) -> bool { *self == 0 }
+                #[inline]
+                fn is_max(&self) -> bool { *self == <$t>::MAX }
+                #[inline]
+                fn saturating_inc(&mut self) { *self = self.saturating_add(1); }
+                #[inline]
+                fn saturating_dec(&mut self) { *self = self.saturating_sub(1); }
+                #[inline]
+                fn as_u64(&self) -> u64 { *self as u64 }
+            }
+        )+
+    };
+}
+
+impl_counter!(u8, u16, u32);
+
+/// A Bloom filter that backs every position with an N-bit saturating counter
+/// instead of a single bit, so members can be removed without introducing false
+/// negatives. The counter width is chosen by the type parameter `C` (defaulting
+/// to `u8`); widen it to `u16`/`u32` when many items are expected to collide on
+/// the same position and `u8` would saturate.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct CountingBloomFilter<C: Counter = u8> {
+    config: FilterBuilder,
+    counters: Vec<C>,
+}
+
+impl<C: Counter> CountingBloomFilter<C> {
+    /// Builds a counting filter from a completed [`FilterBuilder`], allocating one
+    /// counter per bit of the filter's `size`.
+    pub(crate) fn new(mut config: FilterBuilder) -> Self {
+        config.complete();
+        let counters = vec![C::ZERO; config.size as usize];
+        CountingBloomFilter { config, counters }
+    }
+
+    /// Derives the `k` positions an item hashes to using the same pluggable
+    /// [`BloomHashIndex`] backend and Kirsch–Mitzenmacher double-hashing scheme as
+    /// [`BloomFilter`](crate::BloomFilter): the two base hashes come from seeds `0`
+    /// and `1` and are combined into each index as `h1.wrapping_add(i * h2)`.
+    #[inline]
+    fn indices(&self, item: &[u8]) -> impl Iterator<Item = usize> + '_ {
+        let h1 = item.hash_at_index(0);
+        let h2 = item.hash_at_index(1);
+        let size = self.config.size;
+        (0..self.config.hashes as u64)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % size) as usize)
+    }
+
+    /// Adds `item`, incrementing every hashed counter and saturating at the maximum.
+    pub fn add(&mut self, item: &[u8]) {
+        let h1 = item.hash_at_index(0);
+        let h2 = item.hash_at_index(1);
+        let size = self.config.size;
+        for i in 0..self.config.hashes as u64 {
+            let idx = (h1.wrapping_add(i.wrapping_mul(h2)) % size) as usize;
+            self.counters[idx].saturating_inc();
+        }
+    }
+
+    /// Removes `item`, decrementing every hashed counter. A counter that has
+    /// saturated is left untouched: once it overflowed we can no longer tell how
+    /// many members it stands for, and decrementing it could drop another member
+    /// below the floor and cause a false negative.
+    pub fn remove(&mut self, item: &[u8]) {
+        let h1 = item.hash_at_index(0);
+        let h2 = item.hash_at_index(1);
+        let size = self.config.size;
+        for i in 0..self.config.hashes as u64 {
+            let idx = (h1.wrapping_add(i.wrapping_mul(h2)) % size) as usize;
+            let counter = &mut self.counters[idx];
+            if !counter.is_max() {
+                counter.saturating_dec();
+            }
+        }
+    }
+
+    /// Returns `true` iff every counter the item hashes to is nonzero.
+    pub fn contains(&self, item: &[u8]) -> bool {
+        self.indices(item).all(|idx| !self.counters[idx].is_zero())
+    }
+
+    /// Estimates how many times `item` was added, as the minimum counter value
+    /// across its hash positions. Because collisions can only drive a counter
+    /// higher, every counter is an upper bound on the item's true multiplicity,
+    /// so their minimum does not under-count, except that a saturated counter caps
+    /// the estimate at `C::MAX`. Returns `0` when the item is absent.
+    pub fn estimate_count(&self, item: &[u8]) -> u64 {
+        self.indices(item)
+            .map(|idx| self.counters[idx].as_u64())
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+#[test]
+fn counting_remove_test() {
+    let mut counting: CountingBloomFilter = FilterBuilder::new(100_000, 0.01)
+        .build_counting_bloom_filter();
+    counting.add(b"helloworld");
+    counting.add(b"helloworld");
+    assert!(counting.contains(b"helloworld"));
+    assert_eq!(counting.estimate_count(b"helloworld"), 2);
+    counting.remove(b"helloworld");
+    assert!(counting.contains(b"helloworld"));
+    counting.remove(b"helloworld");
+    assert!(!counting.contains(b"helloworld"));
+}
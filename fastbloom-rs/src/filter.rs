@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+
+use crate::builder::FilterBuilder;
+use crate::hash::BloomHashIndex;
+
+/// A classic, space-efficient Bloom filter: a bit array of `size` bits probed at
+/// `hashes` positions per item. Membership queries never return a false negative
+/// (unless [`difference`](Self::difference) has been applied) but may return a
+/// false positive with the probability the [`FilterBuilder`] was configured for.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Serialize, Deserialize)]
+pub struct BloomFilter {
+    config: FilterBuilder,
+    /// The bit array, packed into 32-bit words (`size` is always a multiple of 32).
+    bits: Vec<u32>,
+}
+
+impl BloomFilter {
+    /// Builds an empty filter from a completed [`FilterBuilder`].
+    pub(crate) fn new(mut config: FilterBuilder) -> Self {
+        config.complete();
+        let bits = vec![0u32; (config.size / 32) as usize];
+        BloomFilter { config, bits }
+    }
+
+    /// Derives the `k` positions an item hashes to using the Kirsch–Mitzenmacher
+    /// double-hashing scheme: two base 64-bit hashes are taken once (from seeds `0`
+    /// and `1` of the pluggable [`BloomHashIndex`] backend) and combined into each
+    /// index as `h1.wrapping_add(i * h2)`, avoiding `k` full hashes per item.
+    #[inline]
+    fn indices<T: BloomHashIndex + ?Sized>(&self, item: &T) -> impl Iterator<Item = usize> + '_ {
+        let h1 = item.hash_at_index(0);
+        let h2 = item.hash_at_index(1);
+        let size = self.config.size;
+        // In power-of-two mode `size - 1` is an all-ones mask, so the reduction is a
+        // single AND instead of a modulo.
+        let pow2 = self.config.pow2;
+        (0..self.config.hashes as u64).map(move |i| {
+            let h = h1.wrapping_add(i.wrapping_mul(h2));
+            if pow2 { (h & (size - 1)) as usize } else { (h % size) as usize }
+        })
+    }
+
+    /// Adds `item` to the filter, setting every hashed bit. Accepts any type
+    /// implementing [`BloomHashIndex`], defaulting to the crate's byte hasher.
+    pub fn add<T: BloomHashIndex + ?Sized>(&mut self, item: &T) {
+        // Compute the base hashes locally and loop, rather than routing through the
+        // `&self`-borrowing `indices` iterator: that would borrow-conflict with
+        // `&mut self.bits`, and collecting into a `Vec` to dodge it would allocate on
+        // every call and swamp the masking-vs-modulo difference on the hot path.
+        let h1 = item.hash_at_index(0);
+        let h2 = item.hash_at_index(1);
+        let size = self.config.size;
+        let pow2 = self.config.pow2;
+        for i in 0..self.config.hashes as u64 {
+            let h = h1.wrapping_add(i.wrapping_mul(h2));
+            let idx = if pow2 { (h & (size - 1)) as usize } else { (h % size) as usize };
+            self.bits[idx / 32] |= 1 << (idx % 32);
+        }
+    }
+
+    /// Returns `true` if `item` is possibly in the filter, `false` if it is
+    /// definitely not.
+    pub fn contains<T: BloomHashIndex + ?Sized>(&self, item: &T) -> bool {
+        self.indices(item)
+            .all(|idx| self.bits[idx / 32] & (1 << (idx % 32)) != 0)
+    }
+
+    /// The bit array geometry this filter was built with.
+    #[cfg(test)]
+    pub(crate) fn config(&self) -> &FilterBuilder {
+        &self.config
+    }
+
+    /// Merges `other` into `self` by bitwise-OR of the two bit arrays. Every item
+    /// in either filter is reported as a member afterwards — this is how per-shard
+    /// filters built in parallel are folded into one. Fails if the two filters do
+    /// not share identical `size`/`hashes`.
+    pub fn union(&mut self, other: &BloomFilter) -> Result<(), String> {
+        self.check_compatible(other)?;
+        for (word, rhs) in self.bits.iter_mut().zip(&other.bits) {
+            *word |= *rhs;
+        }
+        Ok(())
+    }
+
+    /// Intersects `self` with `other` by bitwise-AND of the two bit arrays.
+    ///
+    /// This is *approximate*: the result may report members that neither input
+    /// filter truly contains, because a position can be set in both filters by
+    /// unrelated items. It never produces false negatives relative to the true
+    /// intersection. Fails on incompatible geometry.
+    pub fn intersection(&mut self, other: &BloomFilter) -> Result<(), String> {
+        self.check_compatible(other)?;
+        for (word, rhs) in self.bits.iter_mut().zip(&other.bits) {
+            *word &= *rhs;
+        }
+        Ok(())
+    }
+
+    /// Removes `other`'s bits from `self` by bitwise AND-NOT.
+    ///
+    /// Like [`intersection`](Self::intersection) this is approximate, and it can in
+    /// addition introduce false negatives for members of `self` that happen to
+    /// share a position with `other`; use it only when that trade-off is
+    /// acceptable. Fails on incompatible geometry.
+    pub fn difference(&mut self, other: &BloomFilter) -> Result<(), String> {
+        self.check_compatible(other)?;
+        for (word, rhs) in self.bits.iter_mut().zip(&other.bits) {
+            *word &= !*rhs;
+        }
+        Ok(())
+    }
+
+    /// Returns the union of `self` and `other` without mutating either.
+    pub fn union_clone(&self, other: &BloomFilter) -> Result<BloomFilter, String> {
+        let mut out = self.clone();
+        out.union(other)?;
+        Ok(out)
+    }
+
+    /// Returns the intersection of `self` and `other` without mutating either.
+    /// Carries the same approximation caveat as [`intersection`](Self::intersection).
+    pub fn intersection_clone(&self, other: &BloomFilter) -> Result<BloomFilter, String> {
+        let mut out = self.clone();
+        out.intersection(other)?;
+        Ok(out)
+    }
+
+    fn check_compatible(&self, other: &BloomFilter) -> Result<(), String> {
+        if self.config.is_compatible_to(&other.config) {
+            Ok(())
+        } else {
+            Err(format!(
+                "incompatible filters: size {}/{} hashes {}/{}",
+                self.config.size, other.config.size, self.config.hashes, other.config.hashes
+            ))
+        }
+    }
+
+    /// Serializes just the raw bit array into little-endian bytes, ready to be
+    /// shipped over the wire or stored in a file. Pair it with a [`FilterBuilder`]
+    /// of identical geometry and [`BloomFilter::from_bytes`] to rehydrate.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.bits.len() * 4);
+        for word in &self.bits {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Rehydrates a filter from a raw bitset produced by [`BloomFilter::to_bytes`]
+    /// and a builder describing its geometry. Fails if the byte length does not
+    /// match the builder's `size` (there are `size / 8` bytes in a `size`-bit
+    /// filter).
+    pub fn from_bytes(bytes: &[u8], mut builder: FilterBuilder) -> Result<Self, String> {
+        builder.complete();
+        let expected = (builder.size / 8) as usize;
+        if bytes.len() != expected {
+            return Err(format!(
+                "byte length {} does not match filter size {} bits ({} bytes)",
+                bytes.len(),
+                builder.size,
+                expected
+            ));
+        }
+        let bits = bytes
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+        Ok(BloomFilter { config: builder, bits })
+    }
+}
+
+#[test]
+fn bytes_round_trip_test() {
+    let mut bloom = FilterBuilder::new(100_000, 0.01).build_bloom_filter();
+    bloom.add(b"helloworld");
+    let bytes = bloom.to_bytes();
+    let builder = FilterBuilder::from_size_and_hashes(bloom.config().size, bloom.config().hashes);
+    let restored = BloomFilter::from_bytes(&bytes, builder).unwrap();
+    assert!(restored.contains(b"helloworld"));
+    assert!(!restored.contains(b"helloworld!"));
+}
+
+#[test]
+fn union_test() {
+    let mut a = FilterBuilder::new(100_000, 0.01).build_bloom_filter();
+    let mut b = FilterBuilder::new(100_000, 0.01).build_bloom_filter();
+    a.add(b"hello");
+    b.add(b"world");
+    let merged = a.union_clone(&b).unwrap();
+    assert!(merged.contains(b"hello"));
+    assert!(merged.contains(b"world"));
+
+    let mut other = FilterBuilder::new(200_000, 0.01).build_bloom_filter();
+    other.add(b"world");
+    assert!(a.union(&other).is_err());
+}
@@ -0,0 +1,24 @@
+use fastmurmur3::murmur3_x64_128;
+
+/// A pluggable hash backend for Bloom filter members.
+///
+/// A type implementing this yields a 64-bit hash for a given `seed`, letting
+/// callers swap in SipHash13, FxHash, xxHash, or a domain-specific hash. The
+/// filter only ever needs two seeds (`0` and `1`) to derive its two base hashes
+/// and then combines them with the Kirsch–Mitzenmacher double-hashing scheme, so
+/// a full hash is computed at most twice per item regardless of `hashes`.
+pub trait BloomHashIndex {
+    /// Returns the hash of `self` for the given `seed`. Distinct seeds must yield
+    /// (effectively) independent hashes.
+    fn hash_at_index(&self, seed: u64) -> u64;
+}
+
+/// The default backend: any byte-slice-like value is hashed with the same
+/// `murmur3_x64_128` hasher the rest of the crate uses, keyed by `seed`. This
+/// keeps existing `&[u8]`/`&[u8; N]` call sites working unchanged.
+impl<T: AsRef<[u8]> + ?Sized> BloomHashIndex for T {
+    #[inline]
+    fn hash_at_index(&self, seed: u64) -> u64 {
+        murmur3_x64_128(self.as_ref(), seed as u32) as u64
+    }
+}
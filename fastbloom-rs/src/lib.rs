@@ -0,0 +1,9 @@
+mod builder;
+mod counting;
+mod filter;
+mod hash;
+
+pub use builder::FilterBuilder;
+pub use counting::{Counter, CountingBloomFilter};
+pub use filter::BloomFilter;
+pub use hash::BloomHashIndex;